@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serenity::http::Http;
+use serenity::model::prelude::interaction::message_component::MessageComponentInteraction;
+
+use tracing::warn;
+
+use crate::Result;
+
+/// A boxed, type-erased component handler. Handlers are registered against
+/// a `custom_id` prefix and invoked with the `Http` instance and the
+/// triggering interaction whenever a component click matches that prefix.
+pub type HandlerFn = Box<
+  dyn Fn(Arc<Http>, MessageComponentInteraction) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync
+>;
+
+struct Registration {
+  handler: HandlerFn,
+  registered_at: Instant,
+  ttl: Duration
+}
+
+/// Routes incoming `MessageComponentInteraction`s to handlers registered
+/// against the leading segment of their `custom_id` (split on `:`). This
+/// lets a single command register a button pair or a select menu and react
+/// to it later without the caller having to match on `custom_id` by hand.
+///
+/// Registrations expire after their `ttl` so long-lived subsystems (e.g.
+/// pagination) don't grow this map forever; expired entries are swept
+/// lazily on the next `dispatch` call.
+#[derive(Default)]
+pub struct ComponentDispatcher {
+  handlers: HashMap<String, Registration>
+}
+
+impl ComponentDispatcher {
+  pub fn new() -> Self {
+    Self { handlers: HashMap::new() }
+  }
+
+  /// Registers `handler` for every component whose `custom_id` starts with
+  /// `prefix` followed by a `:` separator, until `ttl` elapses.
+  pub fn register(&mut self, prefix: impl Into<String>, ttl: Duration, handler: HandlerFn) {
+    self.handlers.insert(prefix.into(), Registration { handler, registered_at: Instant::now(), ttl });
+  }
+
+  /// Removes a handler before its `ttl` elapses, e.g. once a command knows
+  /// its message can no longer be interacted with.
+  pub fn unregister(&mut self, prefix: &str) -> bool {
+    self.handlers.remove(prefix).is_some()
+  }
+
+  fn sweep_expired(&mut self) {
+    self.handlers.retain(|_, registration| registration.registered_at.elapsed() < registration.ttl);
+  }
+
+  pub async fn dispatch(&mut self, http: &Arc<Http>, interaction: MessageComponentInteraction) -> Result<()> {
+    self.sweep_expired();
+
+    let prefix = dispatch_prefix(&interaction.data.custom_id);
+
+    if let Some(registration) = self.handlers.get(&prefix) {
+      (registration.handler)(Arc::clone(http), interaction).await
+    } else {
+      warn!("No component handler registered for custom_id prefix '{}'", prefix);
+      Ok(())
+    }
+  }
+}
+
+/// The registration prefix a `custom_id` dispatches to: the segment before
+/// its first `:`, or the whole string if it has none.
+fn dispatch_prefix(custom_id: &str) -> String {
+  custom_id.split(':').next().unwrap_or(custom_id).to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn registration(registered_at: Instant, ttl: Duration) -> Registration {
+    Registration { handler: Box::new(|_, _| Box::pin(async { Ok(()) })), registered_at, ttl }
+  }
+
+  #[test]
+  fn sweep_expired_removes_only_registrations_past_their_ttl() {
+    let mut dispatcher = ComponentDispatcher::new();
+    dispatcher.handlers.insert("fresh".to_owned(), registration(Instant::now(), Duration::from_secs(60)));
+    dispatcher.handlers.insert("stale".to_owned(), registration(Instant::now() - Duration::from_secs(120), Duration::from_secs(60)));
+
+    dispatcher.sweep_expired();
+
+    assert!(dispatcher.handlers.contains_key("fresh"));
+    assert!(!dispatcher.handlers.contains_key("stale"));
+  }
+
+  #[test]
+  fn dispatch_prefix_matches_the_segment_before_the_first_colon() {
+    assert_eq!(dispatch_prefix("paginate-guild-42:next:3"), "paginate-guild-42");
+    assert_eq!(dispatch_prefix("no-colons-here"), "no-colons-here");
+  }
+}
@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
 use serenity::async_trait;
-use serenity::builder::CreateInteractionResponseData;
+use serenity::builder::{CreateInteractionResponseData, CreateInteractionResponseFollowup, EditInteractionResponse};
 use serenity::http::Http;
+use serenity::json::Value;
 use serenity::model::prelude::{Attachment, PartialChannel, Role, PartialMember};
 use serenity::model::prelude::command::CommandOptionType;
+use serenity::model::prelude::interaction::InteractionResponseType;
 use serenity::model::prelude::interaction::application_command::{ApplicationCommandInteraction, CommandDataOptionValue, CommandDataOption};
 use serenity::model::user::User;
 
@@ -14,8 +16,36 @@ use crate::Result;
 
 #[async_trait]
 pub trait BetterResponse {
+  /// Sends a normal interaction response. `reply` builds the response body
+  /// and may freely attach action rows (buttons, select menus) via
+  /// `CreateInteractionResponseData::components`—pair those with a
+  /// [`crate::component::ComponentDispatcher`] to react to the resulting
+  /// `MessageComponentInteraction`s.
   async fn reply<'a, ReplyFn>(&self, http: &Arc<Http>, reply: ReplyFn) -> Result<()>
     where for<'b> ReplyFn: FnOnce(&'b mut CreateInteractionResponseData<'a>) -> &'b mut CreateInteractionResponseData<'a> + Send;
+
+  /// Immediately acknowledges the interaction without committing to a
+  /// reply body yet. Use this when a command needs longer than Discord's
+  /// 3-second window to respond, then follow up with [`BetterResponse::followup`]
+  /// once the work is done.
+  async fn defer(&self, http: &Arc<Http>, ephemeral: bool) -> Result<()>;
+
+  /// Sends a message after a prior [`BetterResponse::defer`], mirroring the
+  /// `reply` builder pattern but against the follow-up message builder.
+  async fn followup<'a, ReplyFn>(&self, http: &Arc<Http>, reply: ReplyFn) -> Result<()>
+    where for<'b> ReplyFn: FnOnce(&'b mut CreateInteractionResponseFollowup<'a>) -> &'b mut CreateInteractionResponseFollowup<'a> + Send;
+
+  /// Replaces the original deferred "thinking…" placeholder in place, mirroring
+  /// the `reply` builder pattern but against the edit-original-response builder.
+  /// Use this instead of [`BetterResponse::followup`] when the final reply
+  /// should take the placeholder's spot rather than appear alongside it.
+  async fn edit_original_response<'a, ReplyFn>(&self, http: &Arc<Http>, reply: ReplyFn) -> Result<()>
+    where for<'b> ReplyFn: FnOnce(&'b mut EditInteractionResponse) -> &'b mut EditInteractionResponse + Send;
+
+  /// Responds to an autocomplete interaction with up to 25 `(name, value)`
+  /// choice pairs. Extra choices beyond the 25th are silently dropped, as
+  /// Discord rejects the response otherwise.
+  async fn autocomplete_respond(&self, http: &Arc<Http>, choices: Vec<(String, String)>) -> Result<()>;
 }
 
 #[async_trait]
@@ -31,11 +61,60 @@ impl BetterResponse for ApplicationCommandInteraction {
 
     Ok(())
   }
+
+  async fn defer(&self, http: &Arc<Http>, ephemeral: bool) -> Result<()> {
+    if let Err(why) = self.create_interaction_response(http, |response| {
+      response
+        .kind(InteractionResponseType::DeferredChannelMessageWithSource)
+        .interaction_response_data(|message| message.ephemeral(ephemeral))
+    }).await {
+      error!("Encountered an error while deferring chat command:\n{:?}", why);
+    }
+
+    Ok(())
+  }
+
+  async fn followup<'a, ReplyFn>(&self, http: &Arc<Http>, reply: ReplyFn) -> Result<()>
+    where for<'b> ReplyFn: FnOnce(&'b mut CreateInteractionResponseFollowup<'a>) -> &'b mut CreateInteractionResponseFollowup<'a> + Send
+  {
+    if let Err(why) = self.create_followup_message(http, |message| reply(message)).await {
+      error!("Encountered an error while sending a followup message:\n{:?}", why);
+    }
+
+    Ok(())
+  }
+
+  async fn edit_original_response<'a, ReplyFn>(&self, http: &Arc<Http>, reply: ReplyFn) -> Result<()>
+    where for<'b> ReplyFn: FnOnce(&'b mut EditInteractionResponse) -> &'b mut EditInteractionResponse + Send
+  {
+    if let Err(why) = self.edit_original_interaction_response(http, |message| reply(message)).await {
+      error!("Encountered an error while editing the original interaction response:\n{:?}", why);
+    }
+
+    Ok(())
+  }
+
+  async fn autocomplete_respond(&self, http: &Arc<Http>, choices: Vec<(String, String)>) -> Result<()> {
+    if let Err(why) = self.create_autocomplete_response(http, |response| {
+      for (name, value) in choices.into_iter().take(25) {
+        response.add_string_choice(name, value);
+      }
+
+      response
+    }).await {
+      error!("Encountered an error while responding to autocomplete:\n{:?}", why);
+    }
+
+    Ok(())
+  }
 }
 
 pub trait InteractionCustomGet {
   fn get_subcommand(&self) -> Option<CommandDataOption>;
   fn get_subcommand_group(&self) -> Option<CommandDataOption>;
+  /// Returns the `(name, partial value)` of the option currently focused by
+  /// the user, if this is an autocomplete interaction.
+  fn get_focused(&self) -> Option<(String, String)>;
   fn get_string(&self, name: &str) -> Option<String>;
   fn get_integer(&self, name: &str) -> Option<i64>;
   fn get_bool(&self, name: &str) -> Option<bool>;
@@ -46,44 +125,90 @@ pub trait InteractionCustomGet {
   fn get_attachment(&self, name: &str) -> Option<Attachment>;
 }
 
+// Walks the option tree, descending through any chain of subcommand(-group) nodes,
+// to find the leaf option matching `name`/`kind`. Returns `None` instead of panicking
+// when a subcommand group has no children.
+fn find_value<'a>(options: &'a [CommandDataOption], name: &'a str, kind: CommandOptionType) -> Option<&'a CommandDataOptionValue> {
+  if let Some(found_option) = options.iter().find(|option| option.kind == kind && option.name == name) {
+    let value = found_option.resolved.as_ref().expect("No resolved value exists");
+    return Some(value);
+  }
+
+  for option in options.iter().filter(|option| matches!(option.kind, CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup)) {
+    if let Some(value) = find_value(&option.options, name, kind) {
+      return Some(value);
+    }
+  }
+
+  None
+}
+
 fn get_value<'a>(interaction: &'a ApplicationCommandInteraction, name: &'a str, kind: CommandOptionType) -> Option<&'a CommandDataOptionValue> {
-  // Hoist options
-  let options = if let Some(option) = interaction.data.options.get(0) {
-    match option.kind {
-      CommandOptionType::SubCommand => &option.options,
-      CommandOptionType::SubCommandGroup => &option.options.get(0).unwrap().options,
-      _ => &interaction.data.options
+  find_value(&interaction.data.options, name, kind)
+}
+
+// Descends through any chain of subcommand groups to find the leaf subcommand.
+// Returns `None` instead of panicking when a subcommand group has no children.
+fn find_subcommand(options: &[CommandDataOption]) -> Option<CommandDataOption> {
+  let option = options.iter().find(|option| option.kind == CommandOptionType::SubCommand);
+  if let Some(subcommand) = option {
+    return Some(subcommand.to_owned());
+  }
+
+  for group in options.iter().filter(|option| option.kind == CommandOptionType::SubCommandGroup) {
+    if let Some(subcommand) = find_subcommand(&group.options) {
+      return Some(subcommand);
     }
-  } else { &interaction.data.options };
+  }
 
-  if let Some(found_option) = options.iter().find(|option| option.kind == kind && option.name == name) {
-    let value = found_option.resolved.as_ref().expect("No resolved value exists");
-    Some(value)
-  } else { None }
+  None
+}
+
+fn stringify(value: &Value) -> String {
+  match value {
+    Value::String(value) => value.to_owned(),
+    Value::Number(value) => value.to_string(),
+    Value::Bool(value) => value.to_string(),
+    _ => String::new()
+  }
+}
+
+// Descends through any chain of subcommand(-group) nodes to find the
+// currently-focused option, returning its name and partial value.
+fn find_focused(options: &[CommandDataOption]) -> Option<(String, String)> {
+  let option = options.iter().find(|option| option.focused);
+  if let Some(option) = option {
+    let value = option.value.as_ref().map(stringify).unwrap_or_default();
+    return Some((option.name.clone(), value));
+  }
+
+  for option in options.iter().filter(|option| matches!(option.kind, CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup)) {
+    if let Some(focused) = find_focused(&option.options) {
+      return Some(focused);
+    }
+  }
+
+  None
 }
 
 impl InteractionCustomGet for ApplicationCommandInteraction {
   fn get_subcommand(&self) -> Option<CommandDataOption> {
-    // Hoist potential subcommand group options
-    let options = if let Some(group) = self.data.options.iter().find(|option| option.kind == CommandOptionType::SubCommandGroup) {
-      let mut options = self.data.options.clone();
-      options.extend(group.options.clone());
-      options
-    } else { self.data.options.clone() };
-
-    let option = options.iter().find(|option| option.kind == CommandOptionType::SubCommand);
-    if let Some(subcommand) = option {
-      Some(subcommand.to_owned())
-    } else { None }
+    find_subcommand(&self.data.options)
   }
 
   fn get_subcommand_group(&self) -> Option<CommandDataOption> {
+    // Discord doesn't allow nesting a SubCommandGroup under another one, so unlike
+    // get_value/get_subcommand this never needs to recurse past the top level.
     let option = self.data.options.iter().find(|option| option.kind == CommandOptionType::SubCommandGroup);
     if let Some(subcommand_group) = option {
       Some(subcommand_group.to_owned())
     } else { None }
   }
 
+  fn get_focused(&self) -> Option<(String, String)> {
+    find_focused(&self.data.options)
+  }
+
   fn get_string(&self, name: &str) -> Option<String> {
     if let Some(CommandDataOptionValue::String(value)) = get_value(&self, name, CommandOptionType::String) {
       Some(value.to_owned())
@@ -131,4 +256,83 @@ impl InteractionCustomGet for ApplicationCommandInteraction {
       Some(attachment.to_owned())
     } else { None }
   }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn option(name: &str, kind: CommandOptionType, options: Vec<CommandDataOption>, resolved: Option<CommandDataOptionValue>) -> CommandDataOption {
+    CommandDataOption { name: name.to_owned(), kind, value: None, options, resolved, focused: false }
+  }
+
+  fn focused_option(name: &str, value: Value) -> CommandDataOption {
+    CommandDataOption { name: name.to_owned(), kind: CommandOptionType::String, value: Some(value), options: vec![], resolved: None, focused: true }
+  }
+
+  #[test]
+  fn find_value_returns_none_on_empty_subcommand_group() {
+    let tree = vec![option("group", CommandOptionType::SubCommandGroup, vec![], None)];
+    assert_eq!(find_value(&tree, "foo", CommandOptionType::String), None);
+  }
+
+  #[test]
+  fn find_value_descends_through_nested_subcommand_group() {
+    let leaf = option("foo", CommandOptionType::String, vec![], Some(CommandDataOptionValue::String("bar".into())));
+    let subcommand = option("sub", CommandOptionType::SubCommand, vec![leaf], None);
+    let group = option("group", CommandOptionType::SubCommandGroup, vec![subcommand], None);
+
+    let value = find_value(&[group], "foo", CommandOptionType::String);
+    assert!(matches!(value, Some(CommandDataOptionValue::String(value)) if value == "bar"));
+  }
+
+  #[test]
+  fn find_value_ignores_leaf_of_the_wrong_kind() {
+    let leaf = option("foo", CommandOptionType::Integer, vec![], Some(CommandDataOptionValue::Integer(1)));
+    let subcommand = option("sub", CommandOptionType::SubCommand, vec![leaf], None);
+
+    assert_eq!(find_value(&[subcommand], "foo", CommandOptionType::String), None);
+  }
+
+  #[test]
+  fn find_subcommand_returns_none_on_empty_subcommand_group() {
+    let tree = vec![option("group", CommandOptionType::SubCommandGroup, vec![], None)];
+    assert_eq!(find_subcommand(&tree), None);
+  }
+
+  #[test]
+  fn find_subcommand_descends_through_nested_subcommand_group() {
+    let subcommand = option("sub", CommandOptionType::SubCommand, vec![], None);
+    let group = option("group", CommandOptionType::SubCommandGroup, vec![subcommand.clone()], None);
+
+    assert_eq!(find_subcommand(&[group]), Some(subcommand));
+  }
+
+  #[test]
+  fn find_focused_returns_none_when_nothing_is_focused() {
+    let tree = vec![option("group", CommandOptionType::SubCommandGroup, vec![], None)];
+    assert_eq!(find_focused(&tree), None);
+  }
+
+  #[test]
+  fn find_focused_descends_through_nested_subcommand_group() {
+    let leaf = focused_option("foo", Value::String("ba".into()));
+    let subcommand = option("sub", CommandOptionType::SubCommand, vec![leaf], None);
+    let group = option("group", CommandOptionType::SubCommandGroup, vec![subcommand], None);
+
+    assert_eq!(find_focused(&[group]), Some(("foo".to_owned(), "ba".to_owned())));
+  }
+
+  #[test]
+  fn stringify_formats_string_number_and_bool() {
+    assert_eq!(stringify(&Value::String("bar".into())), "bar");
+    assert_eq!(stringify(&Value::from(5)), "5");
+    assert_eq!(stringify(&Value::Bool(true)), "true");
+  }
+
+  #[test]
+  fn stringify_falls_back_to_empty_string_for_other_kinds() {
+    assert_eq!(stringify(&Value::Null), "");
+    assert_eq!(stringify(&Value::Array(vec![])), "");
+  }
 }
\ No newline at end of file
@@ -0,0 +1,237 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use serenity::builder::{CreateActionRow, CreateEmbed};
+use serenity::http::Http;
+use serenity::model::prelude::component::ButtonStyle;
+use serenity::model::prelude::interaction::InteractionResponseType;
+use serenity::model::prelude::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::prelude::interaction::message_component::MessageComponentInteraction;
+
+use tracing::error;
+
+use crate::Result;
+use crate::component::ComponentDispatcher;
+use crate::interaction::BetterResponse;
+
+const PREFIX: &str = "paginate";
+
+/// How long a pagination session stays interactive before its
+/// `ComponentDispatcher` registration is swept, matching how long Discord
+/// lets a bot edit the original interaction response for.
+const TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Sends `pages` as the first reply to `interaction`, laid out with
+/// first/prev/next/last navigation buttons, and registers the handlers
+/// needed to advance it on `dispatcher`. Edge buttons are disabled when
+/// there's nowhere left to go in that direction.
+///
+/// `session_id` must be unique among pagination sessions still live on
+/// `dispatcher` (i.e. until its `TTL` expires)—reusing one overwrites the
+/// earlier session's handler, so clicks on the earlier message's
+/// now-orphaned buttons get dispatched against this call's `pages` instead.
+pub async fn paginate(
+  http: &Arc<Http>,
+  interaction: &ApplicationCommandInteraction,
+  dispatcher: &mut ComponentDispatcher,
+  session_id: impl Into<String>,
+  pages: Vec<CreateEmbed>
+) -> Result<()> {
+  let session_id = session_id.into();
+
+  if pages.is_empty() {
+    error!("paginate called with zero pages for session '{}'", session_id);
+    return interaction.reply(http, |message| message.ephemeral(true).content("Nothing to show.")).await;
+  }
+
+  let pages = Arc::new(pages);
+
+  interaction.reply(http, |message| {
+    message
+      .set_embed(pages[0].clone())
+      .components(|components| components.create_action_row(|row| nav_row(row, &session_id, 0, pages.len())))
+  }).await?;
+
+  let key = dispatcher_key(&session_id);
+  let pages = Arc::clone(&pages);
+  dispatcher.register(key, TTL, Box::new(move |http, component| {
+    let pages = Arc::clone(&pages);
+    Box::pin(async move { advance(http, component, pages).await })
+  }));
+
+  Ok(())
+}
+
+/// The page `action` navigates to from `page`, clamped to `last`. `page`
+/// comes from the clicked button's `custom_id`, which can be stale if
+/// `session_id` was reused for a shorter-lived `paginate()` call in the
+/// meantime (callers must keep session ids unique per live pagination), so
+/// every arm is clamped to the current page count rather than just `"next"`/`"last"`.
+fn next_page(action: &str, page: usize, last: usize) -> usize {
+  match action {
+    "first" => 0,
+    "prev" => page.saturating_sub(1).min(last),
+    "next" => (page + 1).min(last),
+    "last" => last,
+    _ => page.min(last)
+  }
+}
+
+async fn advance(http: Arc<Http>, interaction: MessageComponentInteraction, pages: Arc<Vec<CreateEmbed>>) -> Result<()> {
+  let parsed = parse_custom_id(&interaction.data.custom_id);
+  let (action, session_id, page) = if let Some(parsed) = parsed { parsed } else { return Ok(()) };
+
+  let next = next_page(&action, page, pages.len() - 1);
+
+  if let Err(why) = interaction.create_interaction_response(&http, |response| {
+    response
+      .kind(InteractionResponseType::UpdateMessage)
+      .interaction_response_data(|message| {
+        message
+          .set_embed(pages[next].clone())
+          .components(|components| components.create_action_row(|row| nav_row(row, &session_id, next, pages.len())))
+      })
+  }).await {
+    error!("Encountered an error while advancing a paginated response:\n{:?}", why);
+  }
+
+  Ok(())
+}
+
+fn nav_row<'a>(row: &'a mut CreateActionRow, session_id: &str, page: usize, total: usize) -> &'a mut CreateActionRow {
+  let (at_start, at_end) = edge_disabled(page, total - 1);
+
+  row
+    .create_button(|button| {
+      button
+        .custom_id(custom_id(session_id, "first", page))
+        .label("« First")
+        .style(ButtonStyle::Secondary)
+        .disabled(at_start)
+    })
+    .create_button(|button| {
+      button
+        .custom_id(custom_id(session_id, "prev", page))
+        .label("‹ Prev")
+        .style(ButtonStyle::Secondary)
+        .disabled(at_start)
+    })
+    .create_button(|button| {
+      button
+        .custom_id(custom_id(session_id, "next", page))
+        .label("Next ›")
+        .style(ButtonStyle::Secondary)
+        .disabled(at_end)
+    })
+    .create_button(|button| {
+      button
+        .custom_id(custom_id(session_id, "last", page))
+        .label("Last »")
+        .style(ButtonStyle::Secondary)
+        .disabled(at_end)
+    })
+}
+
+/// Whether the first/prev buttons and the next/last buttons should be
+/// disabled for `page` out of `last`, i.e. whether there's nowhere left to
+/// go in that direction.
+fn edge_disabled(page: usize, last: usize) -> (bool, bool) {
+  (page == 0, page == last)
+}
+
+/// The `ComponentDispatcher` key for a given pagination session. `dispatch`
+/// only matches on the segment before the first `:`, so the session id has
+/// to live there rather than further along the `custom_id`—otherwise two
+/// concurrently paginated messages would overwrite each other's handler. A
+/// `session_id` containing `:` would split across that boundary and make
+/// the registered key and the dispatched prefix diverge, so it's percent-
+/// encoded here rather than trusting callers not to pass one through.
+fn dispatcher_key(session_id: &str) -> String {
+  format!("{}-{}", PREFIX, encode_session_id(session_id))
+}
+
+fn custom_id(session_id: &str, action: &str, page: usize) -> String {
+  format!("{}:{}:{}", dispatcher_key(session_id), action, page)
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<(String, String, usize)> {
+  let mut parts = custom_id.splitn(3, ':');
+  let session_id = parts.next()?.strip_prefix(&format!("{}-", PREFIX))?;
+  let session_id = decode_session_id(session_id);
+  let action = parts.next()?.to_owned();
+  let page = parts.next()?.parse().ok()?;
+
+  Some((action, session_id, page))
+}
+
+/// Escapes `%` and `:` so a `session_id` can never introduce a `:` into the
+/// `custom_id`'s delimiter-bearing segments. Order matters: `%` is escaped
+/// before `:` so the `%3A` inserted for a literal `:` never gets re-escaped.
+fn encode_session_id(session_id: &str) -> String {
+  session_id.replace('%', "%25").replace(':', "%3A")
+}
+
+/// Reverses [`encode_session_id`]. Order matters: `%3A` is unescaped before
+/// `%25` so a literal `:` is restored before any leftover `%` is.
+fn decode_session_id(session_id: &str) -> String {
+  session_id.replace("%3A", ":").replace("%25", "%")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn custom_id_round_trips_through_parse_custom_id() {
+    let id = custom_id("guild-42", "next", 3);
+    assert_eq!(parse_custom_id(&id), Some(("next".to_owned(), "guild-42".to_owned(), 3)));
+  }
+
+  #[test]
+  fn custom_id_round_trips_when_session_id_contains_a_colon() {
+    let session_id = "123456:789012";
+    let id = custom_id(session_id, "prev", 1);
+
+    assert!(!id.starts_with(&format!("{}-{}", PREFIX, session_id)), "colon in session_id must not reach the custom_id unescaped");
+    assert_eq!(parse_custom_id(&id), Some(("prev".to_owned(), session_id.to_owned(), 1)));
+  }
+
+  #[test]
+  fn parse_custom_id_rejects_a_foreign_prefix() {
+    assert_eq!(parse_custom_id("not-paginate-guild-42:next:3"), None);
+  }
+
+  #[test]
+  fn encode_session_id_round_trips_through_decode_session_id() {
+    for session_id in ["guild-42", "123456:789012", "100%:done", "a%3Ab"] {
+      assert_eq!(decode_session_id(&encode_session_id(session_id)), session_id);
+    }
+  }
+
+  #[test]
+  fn next_page_clamps_every_arm_to_the_current_last_page() {
+    // A stale "prev"/"last" click from a message whose session_id got reused for a
+    // shorter pagination (see `paginate`'s doc comment) must not index past `last`.
+    assert_eq!(next_page("prev", 9, 1), 1);
+    assert_eq!(next_page("last", 9, 1), 1);
+    assert_eq!(next_page("next", 9, 1), 1);
+    assert_eq!(next_page("first", 9, 1), 0);
+    assert_eq!(next_page("bogus", 9, 1), 1);
+  }
+
+  #[test]
+  fn next_page_navigates_normally_within_bounds() {
+    assert_eq!(next_page("first", 2, 4), 0);
+    assert_eq!(next_page("prev", 2, 4), 1);
+    assert_eq!(next_page("next", 2, 4), 3);
+    assert_eq!(next_page("last", 2, 4), 4);
+  }
+
+  #[test]
+  fn edge_disabled_is_true_only_at_the_first_and_last_page() {
+    assert_eq!(edge_disabled(0, 4), (true, false));
+    assert_eq!(edge_disabled(2, 4), (false, false));
+    assert_eq!(edge_disabled(4, 4), (false, true));
+    assert_eq!(edge_disabled(0, 0), (true, true));
+  }
+}